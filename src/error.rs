@@ -11,6 +11,10 @@ pub(crate) enum Error {
     MissingLicense,
     TargetNotAbsolute(PathBuf),
     Metadata(cargo_metadata::Error),
+    MuslTargetUnsupported(String),
+    VerifyFailed,
+    LicenseViolation { krate: String, license: String },
+    ThirdPartyLicensesUnsupportedForVcs,
 }
 
 impl Display for Error {
@@ -33,6 +37,24 @@ impl Display for Error {
             Error::Metadata(m) => {
                 write!(f, "Failed to gather metadata: {}", m)
             }
+            Error::MuslTargetUnsupported(target) => write!(
+                f,
+                "--musl was passed, but don't know how to derive a MUSL target from \"{}\" (expected it to end in \"-gnu\")",
+                target
+            ),
+            Error::VerifyFailed => write!(
+                f,
+                "Verification failed: either building the container image or running makepkg inside it failed. See the container builder's output above."
+            ),
+            Error::LicenseViolation { krate, license } => write!(
+                f,
+                "{} is licensed under \"{}\", which is not in the configured allow-list.",
+                krate, license
+            ),
+            Error::ThirdPartyLicensesUnsupportedForVcs => write!(
+                f,
+                "[package.metadata.aur] third_party_licenses isn't supported together with a VCS (-git) package: THIRD-PARTY-LICENSES is generated locally and would never reach makepkg's git checkout."
+            ),
         }
     }
 }
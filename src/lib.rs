@@ -1,8 +1,11 @@
 //! Independently testable types and functions.
 
+mod error;
+
 use serde::Deserialize;
 use std::ops::Not;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 /// The git forge in which a project's source code is stored.
 pub enum GitHost {
@@ -11,22 +14,89 @@ pub enum GitHost {
 }
 
 impl GitHost {
-    pub fn source(&self, package: &Package, no_bin: bool) -> String {
+    /// Build the `source=` URL for a release asset.
+    ///
+    /// `arch` is the PKGBUILD architecture name (e.g. `"x86_64"`) the asset
+    /// was built for; `None` asks for the architecture-agnostic source
+    /// tarball instead of a binary one. `compression` must match whatever
+    /// format the asset was actually packed with, since it determines the
+    /// URL's file extension.
+    pub fn source(
+        &self,
+        package: &Package,
+        arch: Option<&str>,
+        compression: CompressionFormat,
+    ) -> String {
         // Expecting binary tarballs to be uploaded with a platform identifier.
-        let platform_identifier = if no_bin { "" } else { "-x86_64" };
+        let platform_identifier = arch.map(|a| format!("-{}", a)).unwrap_or_default();
+        let ext = compression.extension();
         match self {
             GitHost::Github => format!(
-                "{}/releases/download/v$pkgver/{}-$pkgver{}.tar.gz",
-                package.repository, package.name, platform_identifier
+                "{}/releases/download/v$pkgver/{}-$pkgver{}.{}",
+                package.repository, package.name, platform_identifier, ext
             ),
             GitHost::Gitlab => format!(
-                "{}/-/archive/v$pkgver/{}-$pkgver{}.tar.gz",
-                package.repository, package.name, platform_identifier
+                "{}/-/archive/v$pkgver/{}-$pkgver{}.{}",
+                package.repository, package.name, platform_identifier, ext
             ),
         }
     }
 }
 
+/// The archive format used for release tarballs, selectable under
+/// `[package.metadata.aur] compression`.
+///
+/// `gzip` remains the default since every `tar` understands it out of the
+/// box, but Arch packages (and the Rust release manifests) increasingly
+/// favour the smaller archives that `xz` and `zstd` produce.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionFormat {
+    #[default]
+    Gzip,
+    Xz,
+    Zstd,
+}
+
+impl CompressionFormat {
+    /// The tarball file extension this format produces, e.g. `"tar.gz"`.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CompressionFormat::Gzip => "tar.gz",
+            CompressionFormat::Xz => "tar.xz",
+            CompressionFormat::Zstd => "tar.zst",
+        }
+    }
+}
+
+/// The integrity-checksum algorithm to record in the PKGBUILD, selectable
+/// under `[package.metadata.aur] checksum`.
+///
+/// `sha256` remains the default, since it's what `pacman` and the wider AUR
+/// ecosystem expect, but some maintainers would rather rely on the stronger
+/// `sha512` or BLAKE2 digests that `makepkg` equally supports.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    #[default]
+    Sha256,
+    Sha512,
+    #[serde(rename = "b2")]
+    Blake2,
+}
+
+impl ChecksumAlgorithm {
+    /// The PKGBUILD array this algorithm's digests are recorded under, e.g.
+    /// `sha256sums`, `sha512sums`, or `b2sums`.
+    pub fn pkgbuild_key(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "sha256sums",
+            ChecksumAlgorithm::Sha512 => "sha512sums",
+            ChecksumAlgorithm::Blake2 => "b2sums",
+        }
+    }
+}
+
 /// The critical fields read from a `Cargo.toml` and rewritten into a PKGBUILD.
 #[derive(Deserialize, Debug)]
 pub struct Package {
@@ -42,9 +112,17 @@ pub struct Package {
 }
 
 impl Package {
-    /// The name of the binary tarball that should be produced from this `Package`.
-    pub fn tarball(&self, output: &Path) -> PathBuf {
-        output.join(format!("{}-{}-x86_64.tar.gz", self.name, self.version))
+    /// The name of the binary tarball that should be produced from this
+    /// `Package`, for the given PKGBUILD architecture name (e.g. `"x86_64"`)
+    /// and archive format.
+    pub fn tarball(&self, output: &Path, arch: &str, compression: CompressionFormat) -> PathBuf {
+        output.join(format!(
+            "{}-{}-{}.{}",
+            self.name,
+            self.version,
+            arch,
+            compression.extension()
+        ))
     }
     /// The name of the source tarball that should be produced from this `Package`.
     pub fn source_tarball(&self, output: &Path) -> PathBuf {
@@ -69,6 +147,24 @@ impl Package {
             .or(self.documentation.as_deref())
             .unwrap_or(&self.repository)
     }
+
+    /// Derive a `{version}-{shorthash}[-dirty]` string from the current git
+    /// checkout. It is reported when a `-git` PKGBUILD is generated but is
+    /// not written into the PKGBUILD, whose `pkgver()` derives the version
+    /// from the checkout at build time.
+    pub fn vcs_version_suffix(&self) -> Result<String, crate::error::Error> {
+        let shorthash = Command::new("git").args(["rev-parse", "--short", "HEAD"]).output()?;
+        let shorthash = std::str::from_utf8(&shorthash.stdout)?.trim();
+
+        let status = Command::new("git").args(["status", "--porcelain"]).output()?;
+        let dirty = std::str::from_utf8(&status.stdout)?.trim().is_empty().not();
+
+        Ok(if dirty {
+            format!("{}-{}-dirty", self.version, shorthash)
+        } else {
+            format!("{}-{}", self.version, shorthash)
+        })
+    }
 }
 
 // {
@@ -107,29 +203,32 @@ impl Metadata {
                 .as_ref()
                 .is_some_and(|aur| aur.depends.is_empty().not() || aur.optdepends.is_empty().not())
     }
+
+    /// Reconcile which section to read extra dependency information from.
+    /// The format we hope the user is using is:
+    ///
+    /// > [package.metadata.aur]
+    ///
+    /// But version 1.5 originally supported:
+    ///
+    /// > [package.metadata]
+    ///
+    /// To avoid a sudden breakage for users, we support both definition
+    /// locations but favour the newer one.
+    pub fn depends_and_optdepends(&self) -> (&[String], &[String]) {
+        if let Some(aur) = self.aur.as_ref() {
+            (aur.depends.as_slice(), aur.optdepends.as_slice())
+        } else {
+            (self.depends.as_slice(), self.optdepends.as_slice())
+        }
+    }
 }
 
 impl std::fmt::Display for Metadata {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Reconcile which section to read extra dependency information from.
-        // The format we hope the user is using is:
-        //
-        // > [package.metadata.aur]
-        //
-        // But version 1.5 originally supported:
-        //
-        // > [package.metadata]
-        //
-        // To avoid a sudden breakage for users, we support both definition
-        // locations but favour the newer one.
-        //
         // We print a warning to the user elsewhere if they're still using the
-        // old way.
-        let (deps, opts) = if let Some(aur) = self.aur.as_ref() {
-            (aur.depends.as_slice(), aur.optdepends.as_slice())
-        } else {
-            (self.depends.as_slice(), self.optdepends.as_slice())
-        };
+        // old [package.metadata] way.
+        let (deps, opts) = self.depends_and_optdepends();
 
         match deps {
             [middle @ .., last] => {
@@ -172,4 +271,33 @@ pub struct AUR {
     pub files: Vec<(PathBuf, PathBuf)>,
     #[serde(default)]
     pub custom: Vec<String>,
+    /// Rust target triples (e.g. `"aarch64-unknown-linux-gnu"`) to
+    /// cross-build and package. Empty means "just build for the host".
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// Bundle every transitive dependency's `LICENSE`/`NOTICE`/`COPYRIGHT`/
+    /// `AUTHORS` files into a single `THIRD-PARTY-LICENSES` file installed
+    /// alongside the binary.
+    #[serde(default)]
+    pub third_party_licenses: bool,
+    /// Extra SPDX identifiers, beyond the built-in permissive set and the
+    /// crate's own license, that the dependency license audit should accept.
+    #[serde(default)]
+    pub license_allowlist: Vec<String>,
+    /// Generate a VCS (`-git`) PKGBUILD instead of a `-bin` release package.
+    #[serde(default)]
+    pub vcs: bool,
+    /// Base image `--verify` builds its throwaway container `FROM`. Defaults
+    /// to `archlinux:base`.
+    pub verify_image: Option<String>,
+    /// Container tool `--verify` shells out to (`docker`, `podman`, ...).
+    /// Defaults to `docker`.
+    pub verify_builder: Option<String>,
+    /// Archive format for release tarballs. Defaults to `gzip`.
+    #[serde(default)]
+    pub compression: CompressionFormat,
+    /// Integrity checksum algorithm recorded in the PKGBUILD/.SRCINFO.
+    /// Defaults to `sha256`.
+    #[serde(default)]
+    pub checksum: ChecksumAlgorithm,
 }
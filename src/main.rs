@@ -1,17 +1,23 @@
 mod error;
 
 use crate::error::Error;
-use cargo_aur::{GitHost, Package};
+use blake2::{Blake2b512, Digest};
+use cargo_aur::{ChecksumAlgorithm, CompressionFormat, GitHost, Package};
 use cargo_metadata::MetadataCommand;
 use colored::*;
+use flate2::{Compression, GzBuilder};
 use gumdrop::{Options, ParsingStyle};
 use hmac_sha256::Hash;
 use serde::Deserialize;
+use sha2::Sha512;
 use std::fs::{DirEntry, File};
 use std::io::{BufWriter, Write};
 use std::ops::Not;
 use std::path::{Path, PathBuf};
 use std::process::{Command, ExitCode};
+use tar::{Builder, Header};
+use tempfile::TempDir;
+use xz2::write::XzEncoder;
 
 /// Licenses available from the Arch Linux `licenses` package.
 ///
@@ -34,6 +40,30 @@ const LICENSES: &[&str] = &[
     "Unlicense", // Not to be confused with "Unlicensed".
 ];
 
+/// The default license allow-list consulted by the dependency license audit
+/// in [`license_audit`]. These are the permissive licenses the Rust
+/// ecosystem overwhelmingly uses; anything stricter (e.g. a copyleft
+/// license) has to be opted into explicitly via `[package.metadata.aur]
+/// license_allowlist`.
+const PERMISSIVE_LICENSES: &[&str] = &[
+    "MIT",
+    "Apache-2.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "ISC",
+    "Zlib",
+    "Unlicense",
+    "CC0-1.0",
+];
+
+/// The `--verify` container's default base image, if `[package.metadata.aur]
+/// verify_image` isn't set.
+const DEFAULT_VERIFY_IMAGE: &str = "archlinux:base";
+
+/// The `--verify` container's default builder binary, if `[package.metadata.
+/// aur] verify_builder` isn't set.
+const DEFAULT_VERIFY_BUILDER: &str = "docker";
+
 #[derive(Options)]
 struct Args {
     /// Display this help message.
@@ -46,6 +76,21 @@ struct Args {
     musl: bool,
     /// Don't actually build anything.
     dryrun: bool,
+    /// Fail the build if a dependency's license isn't on the allow-list,
+    /// instead of just printing a warning.
+    deny_license_violations: bool,
+    /// Generate a VCS (`-git`) PKGBUILD that builds from the latest commit,
+    /// instead of a `-bin` release package.
+    vcs: bool,
+    /// Comma-separated architectures to cross-build and package (e.g.
+    /// `--arch x86_64,aarch64`), as an alternative to `[package.metadata.aur]
+    /// targets`. Each is expanded to its `-unknown-linux-gnu` (or, with
+    /// `--musl`, `-unknown-linux-musl`) target triple.
+    arch: Option<String>,
+    /// Build the generated PKGBUILD inside a clean container via `makepkg`,
+    /// to catch broken dependencies or a bad `source`/`sha256sums` before
+    /// submitting to the AUR.
+    verify: bool,
     /// Absorbs any extra junk arguments.
     #[options(free)]
     free: Vec<String>,
@@ -66,6 +111,82 @@ impl Config {
             .map(|bin| bin.name.as_str())
             .unwrap_or(self.package.name.as_str())
     }
+
+    /// The target triples to cross-build and package for, read from
+    /// `[package.metadata.aur] targets`. Empty means "just build for the
+    /// host (or the musl target, if `--musl` was passed)".
+    fn targets(&self) -> &[String] {
+        self.package
+            .metadata
+            .as_ref()
+            .and_then(|m| m.aur.as_ref())
+            .map(|a| a.targets.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Did the user opt into bundling dependency licenses via
+    /// `[package.metadata.aur] third_party_licenses`?
+    fn wants_third_party_licenses(&self) -> bool {
+        self.package
+            .metadata
+            .as_ref()
+            .and_then(|m| m.aur.as_ref())
+            .is_some_and(|a| a.third_party_licenses)
+    }
+
+    /// Did the user ask for a VCS (`-git`) PKGBUILD, either via `--vcs` or
+    /// `[package.metadata.aur] vcs = true`?
+    fn wants_vcs(&self, cli_vcs: bool) -> bool {
+        cli_vcs
+            || self
+                .package
+                .metadata
+                .as_ref()
+                .and_then(|m| m.aur.as_ref())
+                .is_some_and(|a| a.vcs)
+    }
+
+    /// The base image the `--verify` container is built `FROM`.
+    fn verify_image(&self) -> &str {
+        self.package
+            .metadata
+            .as_ref()
+            .and_then(|m| m.aur.as_ref())
+            .and_then(|a| a.verify_image.as_deref())
+            .unwrap_or(DEFAULT_VERIFY_IMAGE)
+    }
+
+    /// The container tool the `--verify` step shells out to.
+    fn verify_builder(&self) -> &str {
+        self.package
+            .metadata
+            .as_ref()
+            .and_then(|m| m.aur.as_ref())
+            .and_then(|a| a.verify_builder.as_deref())
+            .unwrap_or(DEFAULT_VERIFY_BUILDER)
+    }
+
+    /// The archive format to pack release tarballs with, read from
+    /// `[package.metadata.aur] compression`. Defaults to `gzip`.
+    fn compression(&self) -> CompressionFormat {
+        self.package
+            .metadata
+            .as_ref()
+            .and_then(|m| m.aur.as_ref())
+            .map(|a| a.compression)
+            .unwrap_or_default()
+    }
+
+    /// The integrity checksum algorithm to record in the PKGBUILD/.SRCINFO,
+    /// read from `[package.metadata.aur] checksum`. Defaults to `sha256`.
+    fn checksum_algorithm(&self) -> ChecksumAlgorithm {
+        self.package
+            .metadata
+            .as_ref()
+            .and_then(|m| m.aur.as_ref())
+            .map(|a| a.checksum)
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -116,6 +237,8 @@ fn work(args: Args) -> Result<(), Error> {
 
     let config = cargo_config()?;
 
+    license_audit(&metadata, &config, args.deny_license_violations)?;
+
     // Warn if the user if still using the old metadata definition style.
     if let Some(metadata) = config.package.metadata.as_ref() {
         if metadata.depends.is_empty().not() || metadata.optdepends.is_empty().not() {
@@ -125,25 +248,316 @@ fn work(args: Args) -> Result<(), Error> {
 
     let license = if must_copy_license(&config.package.license) {
         p("LICENSE file will be installed manually.".bold().yellow());
-        Some(license_file()?)
+        Some(license_file(None)?)
     } else {
         None
     };
 
-    if args.dryrun.not() {
-        release_build(args.musl)?;
-        tarball(args.musl, &cargo_target, &output, license.as_ref(), &config)?;
-        let sha256: String = sha256sum(&config.package, &output)?;
+    // A VCS (`-git`) package builds from a live checkout at install time, so
+    // `THIRD-PARTY-LICENSES` -- generated into a local tempdir on the
+    // maintainer's machine -- would never actually be present for `makepkg`
+    // to install on the end user's machine.
+    if config.wants_vcs(args.vcs) && config.wants_third_party_licenses() {
+        return Err(Error::ThirdPartyLicensesUnsupportedForVcs);
+    }
+
+    // Kept alive for the rest of `work`: the tempdir (and the notices file
+    // inside it) is removed as soon as this is dropped, so we never touch a
+    // fixed path in the user's project root that they might have their own
+    // reasons for keeping around.
+    let third_party = third_party_licenses(&metadata, &config)?;
+    let third_party_path = third_party.as_ref().map(|(_, path)| path.as_path());
+
+    if config.wants_vcs(args.vcs) {
+        // A VCS (`-git`) package builds from a live checkout at install
+        // time, so there's no local build/tarball/sha256sum pipeline to run
+        // here at all -- just the PKGBUILD itself.
+        let suffix = config.package.vcs_version_suffix()?;
+        p(format!("Generating VCS PKGBUILD against {}...", suffix).bold());
+
+        let path = output.join("PKGBUILD");
+        let file = BufWriter::new(File::create(path)?);
+        pkgbuild(file, &config, &[], license.as_ref(), third_party_path, true)?;
+
+        let srcinfo_path = output.join(".SRCINFO");
+        let srcinfo_file = BufWriter::new(File::create(srcinfo_path)?);
+        srcinfo(srcinfo_file, &config, &[], true)?;
+
+        if args.verify {
+            verify(&output, &config)?;
+        }
+    } else if args.dryrun.not() {
+        // `--arch` is a CLI-only shorthand for `[package.metadata.aur]
+        // targets`; when given, it takes precedence over the config file.
+        let cli_targets: Vec<String> = args
+            .arch
+            .as_deref()
+            .map(|archs| {
+                archs
+                    .split(',')
+                    .map(|a| a.trim())
+                    .filter(|a| a.is_empty().not())
+                    .map(target_for_arch)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let config_targets = config.targets();
+        let targets: &[String] = if args.arch.is_some() { &cli_targets } else { config_targets };
+
+        // One (arch, checksum) pair per tarball we produce.
+        let sums: Vec<(String, String)> = if targets.is_empty() {
+            // No explicit targets configured: build once, same as before
+            // `[package.metadata.aur] targets` existed.
+            let target = args.musl.then_some("x86_64-unknown-linux-musl");
+            release_build(target)?;
+            let release_dir = target.map_or_else(|| "release".to_string(), |t| format!("{t}/release"));
+            let sha256 = tarball(
+                &release_dir,
+                "x86_64",
+                &cargo_target,
+                &output,
+                license.as_ref(),
+                third_party_path,
+                &config,
+            )?;
+            vec![("x86_64".to_string(), sha256)]
+        } else {
+            targets
+                .iter()
+                .map(|target| {
+                    let target = if args.musl { musl_target(target)? } else { target.clone() };
+                    release_build(Some(target.as_str()))?;
+                    let arch = arch_name(&target);
+                    let release_dir = format!("{target}/release");
+                    let sha256 = tarball(
+                        &release_dir,
+                        arch,
+                        &cargo_target,
+                        &output,
+                        license.as_ref(),
+                        third_party_path,
+                        &config,
+                    )?;
+                    Ok::<(String, String), Error>((arch.to_string(), sha256))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
 
         // Write the PKGBUILD.
         let path = output.join("PKGBUILD");
         let file = BufWriter::new(File::create(path)?);
-        pkgbuild(file, &config, &sha256, license.as_ref())?;
+        pkgbuild(
+            file,
+            &config,
+            &sums,
+            license.as_ref(),
+            third_party_path,
+            false,
+        )?;
+
+        let srcinfo_path = output.join(".SRCINFO");
+        let srcinfo_file = BufWriter::new(File::create(srcinfo_path)?);
+        srcinfo(srcinfo_file, &config, &sums, false)?;
+
+        if args.verify {
+            verify(&output, &config)?;
+        }
     }
 
+    // `third_party`'s `TempDir` (if any) is dropped here, cleaning up the
+    // notices file and its containing tempdir along with it.
     Ok(())
 }
 
+/// Pick out the PKGBUILD architecture name (e.g. `x86_64`, `aarch64`) from
+/// the start of a full Rust target triple (e.g.
+/// `aarch64-unknown-linux-gnu`).
+fn arch_name(target: &str) -> &str {
+    target.split('-').next().unwrap_or(target)
+}
+
+/// Rewrite a configured `[package.metadata.aur] targets` triple to its MUSL
+/// equivalent, so that `--musl` isn't silently ignored for explicit targets.
+/// Only `-gnu` triples are understood; anything else is rejected rather than
+/// silently packaging a dynamically-linked binary the user didn't ask for.
+fn musl_target(target: &str) -> Result<String, Error> {
+    target
+        .strip_suffix("-gnu")
+        .map(|prefix| format!("{prefix}-musl"))
+        .ok_or_else(|| Error::MuslTargetUnsupported(target.to_string()))
+}
+
+/// Expand a bare PKGBUILD architecture name (e.g. `"aarch64"`, as taken by
+/// `--arch`) into its default Linux target triple, the inverse of
+/// [`arch_name`].
+fn target_for_arch(arch: &str) -> String {
+    format!("{arch}-unknown-linux-gnu")
+}
+
+/// Is this an entry that an Arch package should treat as a license notice?
+/// Matched case-insensitively, the way Rust's own `generate-copyright` tool
+/// does.
+fn is_license_ish(file_name: &str) -> bool {
+    let upper = file_name.to_uppercase();
+    ["LICENSE", "LICENCE", "NOTICE", "COPYRIGHT", "AUTHORS"]
+        .iter()
+        .any(|prefix| upper.starts_with(prefix))
+}
+
+/// Every (non-dev) dependency that ends up statically linked into the root
+/// package's binary, found by walking `cargo metadata`'s resolved dependency
+/// graph. Returned sorted by crate name, so callers get deterministic output.
+fn transitive_dependencies(metadata: &cargo_metadata::Metadata) -> Vec<&cargo_metadata::Package> {
+    let (Some(root), Some(resolve)) = (metadata.root_package(), metadata.resolve.as_ref()) else {
+        return Vec::new();
+    };
+
+    let mut seen = std::collections::BTreeSet::new();
+    let mut stack = vec![&root.id];
+    while let Some(id) = stack.pop() {
+        if seen.insert(id.clone()).not() {
+            continue;
+        }
+        if let Some(node) = resolve.nodes.iter().find(|n| &n.id == id) {
+            for dep in &node.deps {
+                // Only follow edges that are actually compiled into the
+                // shipped binary; `dev-dependencies` and `build-dependencies`
+                // never end up statically linked.
+                let normal = dep
+                    .dep_kinds
+                    .iter()
+                    .any(|k| matches!(k.kind, cargo_metadata::DependencyKind::Normal));
+                if normal {
+                    stack.push(&dep.pkg);
+                }
+            }
+        }
+    }
+    seen.remove(&root.id);
+
+    let mut deps: Vec<_> = metadata
+        .packages
+        .iter()
+        .filter(|pkg| seen.contains(&pkg.id))
+        .collect();
+    deps.sort_by(|a, b| a.name.cmp(&b.name));
+    deps
+}
+
+/// Does this SPDX-ish license expression resolve to something in `allowed`?
+/// This is a deliberately small parser: `AND`-separated groups must all be
+/// satisfied, and within a group an `OR` (or legacy `/`) choice needs only
+/// one side to match. Parenthesized groupings (e.g. `"(MIT OR Apache-2.0)
+/// AND BSD-3-Clause"`) are valid SPDX and show up in the wild, so stray
+/// `(`/`)` characters are stripped from each leaf identifier before
+/// comparison.
+fn license_satisfies(expr: &str, allowed: &[String]) -> bool {
+    expr.split(" AND ").all(|group| {
+        group
+            .split('/')
+            .flat_map(|part| part.split(" OR "))
+            .map(|l| l.trim().trim_matches(|c: char| c == '(' || c == ')').trim())
+            .any(|l| allowed.iter().any(|a| a == l))
+    })
+}
+
+/// Audit every transitive dependency's license against an allow-list (the
+/// built-in permissive set, the crate's own license, and anything extra
+/// under `[package.metadata.aur] license_allowlist`). Violations are printed
+/// as warnings, unless `deny` is set, in which case the first one is a hard
+/// error.
+fn license_audit(metadata: &cargo_metadata::Metadata, config: &Config, deny: bool) -> Result<(), Error> {
+    let mut allowed: Vec<String> = PERMISSIVE_LICENSES.iter().map(|s| s.to_string()).collect();
+    allowed.push(config.package.license.clone());
+    if let Some(extra) = config
+        .package
+        .metadata
+        .as_ref()
+        .and_then(|m| m.aur.as_ref())
+        .map(|a| a.license_allowlist.as_slice())
+    {
+        allowed.extend(extra.iter().cloned());
+    }
+
+    for pkg in transitive_dependencies(metadata) {
+        let Some(license) = pkg.license.as_deref() else {
+            continue;
+        };
+        if license_satisfies(license, &allowed).not() {
+            if deny {
+                return Err(Error::LicenseViolation {
+                    krate: pkg.name.clone(),
+                    license: license.to_string(),
+                });
+            }
+            p(format!(
+                "{} {} is licensed under \"{}\", which is outside the configured allow-list.",
+                pkg.name, pkg.version, license
+            )
+            .bold()
+            .yellow());
+        }
+    }
+
+    Ok(())
+}
+
+/// If the user opted in via `[package.metadata.aur] third_party_licenses`,
+/// concatenate every transitive dependency's `LICENSE`/`NOTICE`/
+/// `COPYRIGHT`/`AUTHORS` files into a single deterministic
+/// `THIRD-PARTY-LICENSES` file, written into a fresh tempdir (not the user's
+/// project root, which we have no business writing into or deleting from).
+/// The `TempDir` must be kept alive by the caller for as long as the file is
+/// still needed; it's removed automatically when dropped.
+fn third_party_licenses(
+    metadata: &cargo_metadata::Metadata,
+    config: &Config,
+) -> Result<Option<(TempDir, PathBuf)>, Error> {
+    if config.wants_third_party_licenses().not() {
+        return Ok(None);
+    }
+
+    let mut notices = String::new();
+    for pkg in transitive_dependencies(metadata) {
+        let Some(dir) = pkg.manifest_path.parent() else {
+            continue;
+        };
+        let mut files: Vec<_> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(is_license_ish)
+            })
+            .collect();
+        files.sort_by_key(|entry| entry.file_name());
+
+        for file in files {
+            let Ok(contents) = std::fs::read_to_string(file.path()) else {
+                continue;
+            };
+            notices.push_str(&format!(
+                "{} {} ({})\n",
+                pkg.name,
+                pkg.version,
+                pkg.license.as_deref().unwrap_or("UNKNOWN")
+            ));
+            notices.push_str(&contents);
+            notices.push_str("\n\n");
+        }
+    }
+
+    if notices.is_empty() {
+        return Ok(None);
+    }
+
+    let dir = tempfile::Builder::new().prefix("cargo-aur-third-party-").tempdir()?;
+    let path = dir.path().join("THIRD-PARTY-LICENSES");
+    std::fs::write(&path, notices)?;
+    Ok(Some((dir, path)))
+}
+
 /// Read the `Cargo.toml` for all the fields of concern to this tool.
 fn cargo_config() -> Result<Config, Error> {
     // NOTE 2023-11-27 Yes it looks silly to be reading the whole thing into a
@@ -162,9 +576,10 @@ fn must_copy_license(license: &str) -> bool {
     LICENSES.contains(&license).not()
 }
 
-/// The path to the `LICENSE` file.
-fn license_file() -> Result<DirEntry, Error> {
-    std::fs::read_dir(".")?
+/// The path to the `LICENSE` file in `dir`, or in the current directory when
+/// `dir` is `None`.
+pub(crate) fn license_file(dir: Option<&Path>) -> Result<DirEntry, Error> {
+    std::fs::read_dir(dir.unwrap_or_else(|| Path::new(".")))?
         .filter_map(|entry| entry.ok())
         .find(|entry| {
             entry
@@ -176,12 +591,89 @@ fn license_file() -> Result<DirEntry, Error> {
         .ok_or(Error::MissingLicense)
 }
 
+/// Write the shared tail of a `package()` function: installing the license,
+/// the bundled third-party licenses, and any user-configured `[package.
+/// metadata.aur] files`/`custom` entries. Used by both the binary and VCS
+/// `package()` layouts, which differ only in how the compiled binary itself
+/// is installed.
+fn write_package_extras<T>(
+    file: &mut T,
+    package: &Package,
+    license: Option<&DirEntry>,
+    third_party: Option<&Path>,
+) -> Result<(), Error>
+where
+    T: Write,
+{
+    if let Some(lic) = license {
+        let file_name = lic
+            .file_name()
+            .into_string()
+            .map_err(|_| Error::Utf8OsString)?;
+        writeln!(
+            file,
+            "    install -Dm644 {} \"$pkgdir/usr/share/licenses/$pkgname/{}\"",
+            file_name, file_name
+        )?;
+    }
+
+    if let Some(third_party) = third_party {
+        // Only the basename is meaningful here: it names the archive entry
+        // the tarball actually bundles this file under, not wherever on disk
+        // it happened to be written from (a tempdir, these days).
+        let file_name = third_party
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        writeln!(
+            file,
+            "    install -Dm644 {} \"$pkgdir/usr/share/licenses/$pkgname/{}\"",
+            file_name, file_name
+        )?;
+    }
+
+    if let Some(aur) = package.metadata.as_ref().and_then(|m| m.aur.as_ref()) {
+        for (source, target) in aur.files.iter() {
+            if target.has_root().not() {
+                return Err(Error::TargetNotAbsolute(target.to_path_buf()));
+            } else {
+                writeln!(
+                    file,
+                    "    install -Dm644 \"{}\" \"$pkgdir{}\"",
+                    source.display(),
+                    target.display()
+                )?;
+            }
+        }
+
+        for custom in aur.custom.iter() {
+            writeln!(file, "    {}", custom)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Write a legal PKGBUILD to some `Write` instance (a `File` in this case).
+///
+/// `sums` holds one `(arch, checksum)` pair per tarball that was built. With
+/// exactly one we emit the classic unsuffixed `source=` line plus the
+/// configured checksum array (`sha256sums=` by default); with more than one
+/// we emit the Arch-style split form (`source_x86_64=`, `source_aarch64=`,
+/// etc.). Ignored when `vcs` is set, since a VCS package
+/// builds straight from a git checkout instead of a pre-built tarball.
+///
+/// When `vcs` is set, a `-git` package is emitted instead of a `-bin` one:
+/// a `pkgver()` function derives the version from `git describe`/`rev-list`
+/// at build time, `source`/`sha256sums` point at the live repository, and
+/// `build()`/`package()` compile and install straight from the checkout.
 fn pkgbuild<T>(
     mut file: T,
     config: &Config,
-    sha256: &str,
+    sums: &[(String, String)],
     license: Option<&DirEntry>,
+    third_party: Option<&Path>,
+    vcs: bool,
 ) -> Result<(), Error>
 where
     T: Write,
@@ -193,10 +685,7 @@ where
         .map(|a| format!("# Maintainer: {}", a))
         .collect::<Vec<_>>()
         .join("\n");
-    let source = package
-        .git_host()
-        .unwrap_or(GitHost::Github)
-        .source(&config.package);
+    let git_host = package.git_host().unwrap_or(GitHost::Github);
 
     writeln!(file, "{}", authors)?;
     writeln!(file, "#")?;
@@ -205,15 +694,36 @@ where
         "# This PKGBUILD was generated by `cargo aur`: https://crates.io/crates/cargo-aur"
     )?;
     writeln!(file)?;
-    writeln!(file, "pkgname={}-bin", package.name)?;
-    writeln!(file, "pkgver={}", package.version)?;
+
+    if vcs {
+        writeln!(file, "pkgname={}-git", package.name)?;
+        writeln!(file, "pkgver={}", package.version)?;
+    } else {
+        writeln!(file, "pkgname={}-bin", package.name)?;
+        writeln!(file, "pkgver={}", package.version)?;
+    }
     writeln!(file, "pkgrel=1")?;
     writeln!(file, "pkgdesc=\"{}\"", package.description)?;
     writeln!(file, "url=\"{}\"", package.url())?;
     writeln!(file, "license=(\"{}\")", package.license)?;
-    writeln!(file, "arch=(\"x86_64\")")?;
+
+    if vcs {
+        // A VCS package builds on the user's own machine, so there's no
+        // per-arch binary asset to speak of.
+        writeln!(file, "arch=(\"x86_64\")")?;
+    } else {
+        let archs = sums
+            .iter()
+            .map(|(a, _)| format!("\"{}\"", a))
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(file, "arch=({})", archs)?;
+    }
     writeln!(file, "provides=(\"{}\")", package.name)?;
     writeln!(file, "conflicts=(\"{}\")", package.name)?;
+    if vcs {
+        writeln!(file, "makedepends=(\"cargo\")")?;
+    }
 
     match package.metadata.as_ref() {
         Some(metadata) if metadata.non_empty() => {
@@ -222,57 +732,140 @@ where
         Some(_) | None => {}
     }
 
-    writeln!(file, "source=(\"{}\")", source)?;
-    writeln!(file, "sha256sums=(\"{}\")", sha256)?;
+    if vcs {
+        writeln!(
+            file,
+            "source=(\"{}::git+{}.git\")",
+            package.name, package.repository
+        )?;
+        writeln!(file, "sha256sums=(\"SKIP\")")?;
+    } else {
+        let compression = config.compression();
+        let sums_key = config.checksum_algorithm().pkgbuild_key();
+        for (arch, sum) in sums {
+            let source = git_host.source(&config.package, Some(arch), compression);
+            if sums.len() == 1 {
+                writeln!(file, "source=(\"{}\")", source)?;
+                writeln!(file, "{}=(\"{}\")", sums_key, sum)?;
+            } else {
+                writeln!(file, "source_{}=(\"{}\")", arch, source)?;
+                writeln!(file, "{}_{}=(\"{}\")", sums_key, arch, sum)?;
+            }
+        }
+    }
+
     writeln!(file)?;
-    writeln!(file, "package() {{")?;
-    writeln!(
-        file,
-        "    install -Dm755 {} -t \"$pkgdir/usr/bin\"",
-        config.binary_name()
-    )?;
 
-    if let Some(lic) = license {
-        let file_name = lic
-            .file_name()
-            .into_string()
-            .map_err(|_| Error::Utf8OsString)?;
+    if vcs {
+        writeln!(file, "pkgver() {{")?;
+        writeln!(file, "    cd \"{}\"", package.name)?;
         writeln!(
             file,
-            "    install -Dm644 {} \"$pkgdir/usr/share/licenses/$pkgname/{}\"",
-            file_name, file_name
+            "    git describe --long --tags 2>/dev/null | sed 's/^v//;s/\\([^-]*-g\\)/r\\1/;s/-/./g' ||"
+        )?;
+        writeln!(
+            file,
+            "        printf \"r%s.%s\" \"$(git rev-list --count HEAD)\" \"$(git rev-parse --short HEAD)\""
         )?;
+        writeln!(file, "}}")?;
+        writeln!(file)?;
+        writeln!(file, "build() {{")?;
+        writeln!(file, "    cd \"{}\"", package.name)?;
+        writeln!(file, "    cargo build --release --locked")?;
+        writeln!(file, "}}")?;
+        writeln!(file)?;
+        writeln!(file, "package() {{")?;
+        writeln!(file, "    cd \"{}\"", package.name)?;
+        writeln!(
+            file,
+            "    install -Dm755 \"target/release/{}\" -t \"$pkgdir/usr/bin\"",
+            config.binary_name()
+        )?;
+        write_package_extras(&mut file, package, license, third_party)?;
+        writeln!(file, "}}")?;
+    } else {
+        writeln!(file, "package() {{")?;
+        writeln!(
+            file,
+            "    install -Dm755 {} -t \"$pkgdir/usr/bin\"",
+            config.binary_name()
+        )?;
+        write_package_extras(&mut file, package, license, third_party)?;
+        writeln!(file, "}}")?;
     }
 
-    if let Some(aur) = package.metadata.as_ref().and_then(|m| m.aur.as_ref()) {
-        for (source, target) in aur.files.iter() {
-            if target.has_root().not() {
-                return Err(Error::TargetNotAbsolute(target.to_path_buf()));
-            } else {
-                writeln!(
-                    file,
-                    "    install -Dm644 \"{}\" \"$pkgdir{}\"",
-                    source.display(),
-                    target.display()
-                )?;
-            }
+    Ok(())
+}
+
+/// Write a `.SRCINFO` in the indented `key = value` format the AUR expects,
+/// alongside the `PKGBUILD`. Normally maintainers generate this by hand with
+/// `makepkg --printsrcinfo`; since `work` already has every field resolved,
+/// we can just write it directly instead of shelling out.
+fn srcinfo<T>(mut file: T, config: &Config, sums: &[(String, String)], vcs: bool) -> Result<(), Error>
+where
+    T: Write,
+{
+    let package = &config.package;
+    let git_host = package.git_host().unwrap_or(GitHost::Github);
+    let pkgname = format!("{}-{}", package.name, if vcs { "git" } else { "bin" });
+
+    writeln!(file, "pkgbase = {}", pkgname)?;
+    writeln!(file, "\tpkgdesc = {}", package.description)?;
+    writeln!(file, "\tpkgver = {}", package.version)?;
+    writeln!(file, "\tpkgrel = 1")?;
+    writeln!(file, "\turl = {}", package.url())?;
+
+    if vcs {
+        writeln!(file, "\tarch = x86_64")?;
+    } else {
+        for (arch, _) in sums {
+            writeln!(file, "\tarch = {}", arch)?;
         }
+    }
 
-        for custom in aur.custom.iter() {
-            writeln!(file, "    {}", custom)?;
+    writeln!(file, "\tlicense = {}", package.license)?;
+
+    if let Some(metadata) = package.metadata.as_ref() {
+        let (deps, opts) = metadata.depends_and_optdepends();
+        for dep in deps {
+            writeln!(file, "\tdepends = {}", dep)?;
+        }
+        for opt in opts {
+            writeln!(file, "\toptdepends = {}", opt)?;
+        }
+    }
+
+    if vcs {
+        writeln!(file, "\tsource = {}::git+{}.git", package.name, package.repository)?;
+        writeln!(file, "\tsha256sums = SKIP")?;
+    } else {
+        let compression = config.compression();
+        let sums_key = config.checksum_algorithm().pkgbuild_key();
+        for (arch, sum) in sums {
+            let source = git_host.source(&config.package, Some(arch), compression);
+            if sums.len() == 1 {
+                writeln!(file, "\tsource = {}", source)?;
+                writeln!(file, "\t{} = {}", sums_key, sum)?;
+            } else {
+                writeln!(file, "\tsource_{} = {}", arch, source)?;
+                writeln!(file, "\t{}_{} = {}", sums_key, arch, sum)?;
+            }
         }
     }
 
-    writeln!(file, "}}")?;
+    writeln!(file)?;
+    writeln!(file, "pkgname = {}", pkgname)?;
+
     Ok(())
 }
 
-/// Run `cargo build --release`, potentially building statically.
-fn release_build(musl: bool) -> Result<(), Error> {
-    let mut args = vec!["build", "--release"];
+/// Run `cargo build --release`, either for the host or a specific
+/// cross-compilation target triple.
+fn release_build(target: Option<&str>) -> Result<(), Error> {
+    let mut args = vec!["build".to_string(), "--release".to_string()];
 
-    if musl {
-        args.push("--target=x86_64-unknown-linux-musl");
+    if let Some(target) = target {
+        args.push(format!("--target={target}"));
     }
 
     p("Running release build...".bold());
@@ -280,34 +873,80 @@ fn release_build(musl: bool) -> Result<(), Error> {
     Ok(())
 }
 
+/// The mtime stamped onto every tar entry, so that two builds of the same
+/// sources produce byte-identical tarballs. Honors `SOURCE_DATE_EPOCH` (the
+/// convention used by reproducible-builds tooling), falling back to the Unix
+/// epoch. The gzip header's own mtime is always 0, regardless of this value.
+fn source_date_epoch() -> u64 {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Append a file to the tarball with normalized metadata (fixed mtime, 0/0
+/// uid/gid, no owner/group names) so the resulting archive is reproducible.
+fn append_deterministic<W: Write>(
+    builder: &mut Builder<W>,
+    path: &Path,
+    arcname: &str,
+    mode: u32,
+    mtime: u64,
+) -> Result<(), Error> {
+    let mut header = Header::new_gnu();
+    let data = std::fs::read(path)?;
+    header.set_size(data.len() as u64);
+    header.set_mode(mode);
+    header.set_uid(0);
+    header.set_gid(0);
+    header.set_mtime(mtime);
+    header.set_cksum();
+    builder.append_data(&mut header, arcname, data.as_slice())?;
+    Ok(())
+}
+
+/// Build the release tarball in-process and return its checksum, computed
+/// with the algorithm selected by `[package.metadata.aur] checksum`.
+///
+/// The archive is assembled in memory first (rather than streamed straight to
+/// a `File`) so that the one pass of bytes can both be written to disk and
+/// hashed, instead of writing the tarball and then re-reading it from disk
+/// just to compute its checksum.
 fn tarball(
-    musl: bool,
+    release_dir: &str,
+    arch: &str,
     cargo_target: &Path,
     output: &Path,
     license: Option<&DirEntry>,
+    third_party: Option<&Path>,
     config: &Config,
-) -> Result<(), Error> {
-    let release_dir = if musl {
-        "x86_64-unknown-linux-musl/release"
-    } else {
-        "release"
-    };
-
+) -> Result<String, Error> {
     let binary_name = config.binary_name();
     let binary = cargo_target.join(release_dir).join(binary_name);
 
     strip(&binary)?;
     std::fs::copy(binary, binary_name)?;
 
-    // Create the tarball.
+    // Create the tarball in-process and deterministically, so that repeated
+    // builds of the same sources yield an identical sha256sum.
     p("Packing tarball...".bold());
-    let mut command = Command::new("tar");
-    command
-        .arg("czf")
-        .arg(config.package.tarball(output))
-        .arg(binary_name);
+    let mtime = source_date_epoch();
+
+    // Sorted, stable entry order: (path on disk, name in the archive, mode).
+    let mut entries: Vec<(PathBuf, String, u32)> = vec![(binary_name.into(), binary_name.to_string(), 0o755)];
     if let Some(lic) = license {
-        command.arg(lic.path());
+        let name = lic.file_name().into_string().map_err(|_| Error::Utf8OsString)?;
+        entries.push((lic.path(), name, 0o644));
+    }
+    if let Some(third_party) = third_party {
+        // Archive entries are named by basename, not by the (tempdir) path
+        // the file is actually read from on disk.
+        let name = third_party
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string();
+        entries.push((third_party.to_path_buf(), name, 0o644));
     }
     if let Some(files) = config
         .package
@@ -317,29 +956,135 @@ fn tarball(
         .map(|a| a.files.as_slice())
     {
         for (file, _) in files {
-            command.arg(file);
+            let name = file.to_str().unwrap_or_default().to_string();
+            entries.push((file.clone(), name, 0o644));
         }
     }
-    command.status()?;
+    entries.sort_by(|a, b| a.1.cmp(&b.1));
+
+    // Write the uncompressed tar stream into memory first, then compress it
+    // in whichever format was configured. Keeping the tar-building step
+    // separate from the compression step lets all three formats share the
+    // exact same entry-writing code above.
+    let mut archive = Builder::new(Vec::new());
+    for (path, name, mode) in &entries {
+        append_deterministic(&mut archive, path, name, *mode, mtime)?;
+    }
+    let tar_bytes = archive.into_inner()?;
+
+    let compression = config.compression();
+    let bytes = match compression {
+        // `mtime(0)` keeps the gzip header itself reproducible too.
+        CompressionFormat::Gzip => {
+            let mut enc = GzBuilder::new().mtime(0).write(Vec::new(), Compression::default());
+            enc.write_all(&tar_bytes)?;
+            enc.finish()?
+        }
+        CompressionFormat::Xz => {
+            let mut enc = XzEncoder::new(Vec::new(), 6);
+            enc.write_all(&tar_bytes)?;
+            enc.finish()?
+        }
+        CompressionFormat::Zstd => {
+            let mut enc = zstd::Encoder::new(Vec::new(), 0)?;
+            enc.write_all(&tar_bytes)?;
+            enc.finish()?
+        }
+    };
 
+    std::fs::write(config.package.tarball(output, arch, compression), &bytes)?;
     std::fs::remove_file(binary_name)?;
 
-    Ok(())
+    Ok(checksum(&bytes, config.checksum_algorithm()))
+}
+
+/// Compute the integrity digest of a built tarball, in whichever algorithm
+/// `[package.metadata.aur] checksum` selected.
+fn checksum(bytes: &[u8], algo: ChecksumAlgorithm) -> String {
+    match algo {
+        ChecksumAlgorithm::Sha256 => {
+            let digest = Hash::hash(bytes);
+            digest.iter().map(|u| format!("{:02x}", u)).collect()
+        }
+        ChecksumAlgorithm::Sha512 => {
+            let mut hasher = Sha512::new();
+            hasher.update(bytes);
+            hasher.finalize().iter().map(|u| format!("{:02x}", u)).collect()
+        }
+        ChecksumAlgorithm::Blake2 => {
+            let mut hasher = Blake2b512::new();
+            hasher.update(bytes);
+            hasher.finalize().iter().map(|u| format!("{:02x}", u)).collect()
+        }
+    }
 }
 
 /// Strip the release binary, so that we aren't compressing more bytes than we
-/// need to.
+/// need to. `strip` isn't guaranteed to be installed, so we degrade
+/// gracefully and pack the unstripped binary rather than fail the build.
 fn strip(path: &Path) -> Result<(), Error> {
-    p("Stripping binary...".bold());
-    Command::new("strip").arg(path).status()?;
-    Ok(()) // FIXME Would love to use my `void` package here and elsewhere.
+    match Command::new("strip").arg(path).status() {
+        Ok(status) if status.success() => p("Stripping binary...".bold()),
+        Ok(_) | Err(_) => p("`strip` not available or failed; packing the unstripped binary."
+            .bold()
+            .yellow()),
+    }
+    Ok(())
 }
 
-fn sha256sum(package: &Package, output: &Path) -> Result<String, Error> {
-    let bytes = std::fs::read(package.tarball(output))?;
-    let digest = Hash::hash(&bytes);
-    let hex = digest.iter().map(|u| format!("{:02x}", u)).collect();
-    Ok(hex)
+/// Build the generated PKGBUILD (and whatever tarball/sources it references)
+/// inside a clean throwaway container, the way Arch repo tooling like
+/// Malachite does. Catches broken `depends`, bad `source` URLs, or
+/// mismatched `sha256sums` before the user ever pushes to the AUR.
+fn verify(output: &Path, config: &Config) -> Result<(), Error> {
+    let image = config.verify_image();
+    let builder = config.verify_builder();
+
+    p(format!("Verifying PKGBUILD in a clean {} container...", image).bold());
+
+    let dockerfile = format!(
+        "FROM {image}\n\
+         RUN pacman -Syu --noconfirm --needed base-devel sudo \\\n    \
+         && useradd -m build-user \\\n    \
+         && echo \"build-user ALL=(ALL) NOPASSWD: ALL\" > /etc/sudoers.d/build-user\n\
+         COPY --chown=build-user:build-user . /home/build-user/pkg\n\
+         WORKDIR /home/build-user/pkg\n\
+         USER build-user\n\
+         CMD [\"makepkg\", \"-s\", \"--noconfirm\"]\n"
+    );
+    let dockerfile_path = output.join("Dockerfile.cargo-aur-verify");
+    std::fs::write(&dockerfile_path, dockerfile)?;
+
+    let image_tag = "cargo-aur-verify";
+    let build_success = Command::new(builder)
+        .arg("build")
+        .arg("--file")
+        .arg(&dockerfile_path)
+        .arg("--tag")
+        .arg(image_tag)
+        .arg(output)
+        .status()?
+        .success();
+
+    std::fs::remove_file(&dockerfile_path)?;
+
+    if build_success.not() {
+        return Err(Error::VerifyFailed);
+    }
+
+    let run_success = Command::new(builder)
+        .arg("run")
+        .arg("--rm")
+        .arg(image_tag)
+        .status()?
+        .success();
+
+    if run_success {
+        p("makepkg succeeded inside the container.".bold().green());
+        Ok(())
+    } else {
+        Err(Error::VerifyFailed)
+    }
 }
 
 /// Does the user have the `x86_64-unknown-linux-musl` target installed?
@@ -357,3 +1102,52 @@ fn musl_check() -> Result<(), Error> {
 fn p(msg: ColoredString) {
     println!("{} {}", "::".bold(), msg)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arch_name_strips_vendor_and_abi() {
+        assert_eq!(arch_name("aarch64-unknown-linux-gnu"), "aarch64");
+        assert_eq!(arch_name("x86_64-unknown-linux-musl"), "x86_64");
+        assert_eq!(arch_name("x86_64"), "x86_64");
+    }
+
+    #[test]
+    fn musl_target_rewrites_gnu_triples() {
+        assert_eq!(
+            musl_target("aarch64-unknown-linux-gnu").ok().as_deref(),
+            Some("aarch64-unknown-linux-musl")
+        );
+        assert!(musl_target("x86_64-apple-darwin").is_err());
+    }
+
+    #[test]
+    fn target_for_arch_expands_to_gnu_triple() {
+        assert_eq!(target_for_arch("aarch64"), "aarch64-unknown-linux-gnu");
+        assert_eq!(arch_name(&target_for_arch("x86_64")), "x86_64");
+    }
+
+    #[test]
+    fn is_license_ish_matches_known_notice_files() {
+        assert!(is_license_ish("LICENSE"));
+        assert!(is_license_ish("LICENSE-MIT"));
+        assert!(is_license_ish("license-apache"));
+        assert!(is_license_ish("NOTICE.txt"));
+        assert!(is_license_ish("COPYRIGHT"));
+        assert!(is_license_ish("AUTHORS"));
+        assert!(is_license_ish("Licence"));
+        assert!(!is_license_ish("README.md"));
+        assert!(!is_license_ish("Cargo.toml"));
+    }
+
+    #[test]
+    fn license_satisfies_handles_parenthesized_expressions() {
+        let allowed = ["MIT".to_string(), "Apache-2.0".to_string(), "BSD-3-Clause".to_string()];
+        assert!(license_satisfies("(MIT OR Apache-2.0) AND BSD-3-Clause", &allowed));
+        assert!(license_satisfies("MIT OR Apache-2.0", &allowed));
+        assert!(license_satisfies("MIT/Apache-2.0", &allowed));
+        assert!(!license_satisfies("(GPL-3.0-only OR MIT) AND BSD-3-Clause", &["BSD-3-Clause".to_string()]));
+    }
+}